@@ -0,0 +1,157 @@
+use crate::Result;
+use globset::{GlobBuilder, GlobMatcher};
+use std::path::{Path, PathBuf};
+
+/// Compile a glob with `*` stopping at path separators, like a shell or `.gitignore` glob
+/// (`**` is still needed to cross directories).
+fn compile_matcher(pattern: &str) -> Result<GlobMatcher> {
+    Ok(GlobBuilder::new(pattern).literal_separator(true).build()?.compile_matcher())
+}
+
+/// An include pattern split into a literal base directory and the glob that applies below it,
+/// so the walker can tell whether a directory is even worth descending into before reading it.
+pub struct IncludePattern {
+    base: PathBuf,
+    matcher: GlobMatcher,
+}
+
+impl IncludePattern {
+    fn compile(pattern: &str) -> Result<Self> {
+        let (base, relative) = split_literal_base(pattern);
+        let matcher = compile_matcher(&relative)?;
+        Ok(Self { base, matcher })
+    }
+
+    /// Whether `rel_dir` could still lead to a match: either it's on the way to `base`, or it
+    /// is `base` or lies below it. The glob below `base` is not consulted, so every directory
+    /// under `base` counts as a possible match.
+    fn may_contain_match(&self, rel_dir: &Path) -> bool {
+        rel_dir.starts_with(&self.base) || self.base.starts_with(rel_dir)
+    }
+
+    fn is_match(&self, rel_path: &Path) -> bool {
+        let Ok(below_base) = rel_path.strip_prefix(&self.base) else {
+            return false;
+        };
+        self.matcher.is_match(below_base)
+    }
+}
+
+/// Split a glob pattern like `"assets/images/*.png"` into its literal leading directories
+/// (`"assets/images"`) and the remaining glob (`"*.png"`). A pattern with no literal prefix,
+/// such as `"*.png"`, keeps an empty base (the source directory itself).
+fn split_literal_base(pattern: &str) -> (PathBuf, String) {
+    let is_glob_component = |c: &str| c.contains(['*', '?', '[', '{']);
+
+    let components: Vec<&str> = pattern.split('/').collect();
+    let mut split_at = components.len();
+    for (i, component) in components.iter().enumerate() {
+        if is_glob_component(component) {
+            split_at = i;
+            break;
+        }
+    }
+
+    let base = components[..split_at].iter().collect::<PathBuf>();
+    let relative = components[split_at..].join("/");
+    (base, relative)
+}
+
+/// An exclude pattern, plus a matcher for the directory itself so a subtree like
+/// `node_modules/**` prunes `node_modules` before it's ever `read_dir`'d, not just the files
+/// below it.
+struct ExcludePattern {
+    matcher: GlobMatcher,
+    dir_matcher: GlobMatcher,
+}
+
+impl ExcludePattern {
+    fn compile(pattern: &str) -> Result<Self> {
+        let matcher = compile_matcher(pattern)?;
+        let dir_pattern = pattern.strip_suffix("/**").unwrap_or(pattern);
+        let dir_matcher = compile_matcher(dir_pattern)?;
+        Ok(Self { matcher, dir_matcher })
+    }
+
+    fn is_match(&self, rel_path: &Path) -> bool {
+        self.matcher.is_match(rel_path)
+    }
+
+    /// Whether `rel_dir` itself should be pruned: either it matches the exclude pattern
+    /// directly, or it's the directory root of a `dir/**`-style pattern.
+    fn matches_dir(&self, rel_dir: &Path) -> bool {
+        self.matcher.is_match(rel_dir) || self.dir_matcher.is_match(rel_dir)
+    }
+}
+
+/// Include/exclude glob filters applied while walking the source directory
+#[derive(Default)]
+pub struct PathFilters {
+    includes: Vec<IncludePattern>,
+    excludes: Vec<ExcludePattern>,
+}
+
+impl PathFilters {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Restrict sorting to files matching at least one of these glob patterns, e.g. `"*.png"`
+    /// or `"assets/**/*.jpg"`
+    pub fn include(mut self, patterns: &[&str]) -> Result<Self> {
+        for pattern in patterns {
+            self.includes.push(IncludePattern::compile(pattern)?);
+        }
+        Ok(self)
+    }
+
+    /// Skip files and whole directory subtrees matching any of these glob patterns, e.g.
+    /// `"node_modules/**"` or `".*"`
+    pub fn exclude(mut self, patterns: &[&str]) -> Result<Self> {
+        for pattern in patterns {
+            self.excludes.push(ExcludePattern::compile(pattern)?);
+        }
+        Ok(self)
+    }
+
+    /// Whether a directory at `rel_dir` (relative to the source directory) is worth descending
+    /// into: not excluded, and still on the way to (or past) some include pattern's base.
+    pub fn should_descend(&self, rel_dir: &Path) -> bool {
+        if self.excludes.iter().any(|e| e.matches_dir(rel_dir)) {
+            return false;
+        }
+
+        self.includes.is_empty() || self.includes.iter().any(|p| p.may_contain_match(rel_dir))
+    }
+
+    /// Whether a file at `rel_path` (relative to the source directory) should be sorted
+    pub fn should_include(&self, rel_path: &Path) -> bool {
+        if self.excludes.iter().any(|e| e.is_match(rel_path)) {
+            return false;
+        }
+
+        self.includes.is_empty() || self.includes.iter().any(|p| p.is_match(rel_path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_include_star_does_not_cross_directories() {
+        let filters = PathFilters::new().include(&["*.png"]).unwrap();
+
+        assert!(filters.should_include(Path::new("pic.png")));
+        assert!(!filters.should_include(Path::new("sub/pic.png")));
+    }
+
+    #[test]
+    fn test_exclude_prunes_directory_itself() {
+        let filters = PathFilters::new().exclude(&["node_modules/**"]).unwrap();
+
+        assert!(!filters.should_descend(Path::new("node_modules")));
+        assert!(!filters.should_descend(Path::new("node_modules/pkg")));
+        assert!(filters.should_descend(Path::new("src")));
+    }
+}
@@ -1,12 +1,65 @@
+mod filters;
+mod rules;
+mod watch;
+
+pub use filters::PathFilters;
+pub use rules::{MatchPredicate, Rule, RuleSet};
+
 use crate::Result;
-use std::collections::HashMap;
+use indicatif::ProgressBar;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Default quiet period used to coalesce bursts of filesystem events in [`FileSorter::watch`]
+const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(500);
 
 /// File sorter that organizes files by extension and HTML categories
 pub struct FileSorter {
     source_dir: PathBuf,
     target_dir: PathBuf,
+    recursive: bool,
+    max_depth: Option<usize>,
+    rules: RuleSet,
+    action: SortAction,
+    collision_policy: CollisionPolicy,
+    debounce: Duration,
+    filters: PathFilters,
+    dry_run: bool,
+    show_progress: bool,
+}
+
+/// How a sorted file is placed into its target directory
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortAction {
+    /// Duplicate the file into the target directory, leaving the original in place
+    Copy,
+    /// Move the file into the target directory, removing it from the source
+    Move,
+    /// Create a hard link in the target directory, sharing the same inode as the source
+    Hardlink,
+}
+
+/// What to do when the computed target path already exists
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CollisionPolicy {
+    /// Leave the existing file alone and don't sort the new one
+    Skip,
+    /// Replace the existing file
+    Overwrite,
+    /// Append an incrementing `" (n)"` suffix until a free name is found
+    Rename,
+}
+
+impl SortAction {
+    fn past_tense(&self) -> &'static str {
+        match self {
+            SortAction::Copy => "Copied",
+            SortAction::Move => "Moved",
+            SortAction::Hardlink => "Hardlinked",
+        }
+    }
 }
 
 /// HTML file categories based on content or naming patterns
@@ -25,68 +78,322 @@ impl FileSorter {
         Self {
             source_dir: source_dir.as_ref().to_path_buf(),
             target_dir: target_dir.as_ref().to_path_buf(),
+            recursive: false,
+            max_depth: None,
+            rules: RuleSet::default_rules(),
+            action: SortAction::Copy,
+            collision_policy: CollisionPolicy::Rename,
+            debounce: DEFAULT_DEBOUNCE,
+            filters: PathFilters::new(),
+            dry_run: false,
+            show_progress: false,
         }
     }
 
+    /// Compute the full sorting plan without touching the filesystem
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Show a live progress bar, counting candidate files before sorting begins
+    pub fn show_progress(mut self, show_progress: bool) -> Self {
+        self.show_progress = show_progress;
+        self
+    }
+
+    /// Set how files are placed into the target directory (copy, move, or hardlink)
+    pub fn action(mut self, action: SortAction) -> Self {
+        self.action = action;
+        self
+    }
+
+    /// Set what to do when a file of the same name already exists at the target path
+    pub fn collision_policy(mut self, collision_policy: CollisionPolicy) -> Self {
+        self.collision_policy = collision_policy;
+        self
+    }
+
+    /// Enable recursive traversal of `source_dir`, sorting files found in nested directories too
+    pub fn recursive(mut self, recursive: bool) -> Self {
+        self.recursive = recursive;
+        self
+    }
+
+    /// Limit how many directory levels below `source_dir` are descended into when `recursive` is set
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Replace the built-in sorting rules with a custom rule set
+    pub fn with_rules(mut self, rules: RuleSet) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    /// Load custom sorting rules from a TOML or JSON config file
+    pub fn load_rules<P: AsRef<Path>>(mut self, path: P) -> Result<Self> {
+        self.rules = RuleSet::load_from_file(path)?;
+        Ok(self)
+    }
+
+    /// Set the quiet period `watch()` waits for a path to stop changing before sorting it
+    pub fn debounce(mut self, debounce: Duration) -> Self {
+        self.debounce = debounce;
+        self
+    }
+
+    /// Restrict sorting to files matching at least one of these glob patterns (e.g. `"*.png"`)
+    pub fn include(mut self, patterns: &[&str]) -> Result<Self> {
+        self.filters = self.filters.include(patterns)?;
+        Ok(self)
+    }
+
+    /// Skip files and whole directory subtrees matching any of these glob patterns (e.g.
+    /// `"node_modules/**"`)
+    pub fn exclude(mut self, patterns: &[&str]) -> Result<Self> {
+        self.filters = self.filters.exclude(patterns)?;
+        Ok(self)
+    }
+
     /// Sort files by extension and HTML categories
     pub fn sort_files(&self) -> Result<SortingReport> {
         let mut report = SortingReport::new();
-        
-        // Ensure target directory exists
-        fs::create_dir_all(&self.target_dir)?;
+        // Target paths already claimed by this run, so a dry run (which never actually creates
+        // files) still detects collisions between files it plans to sort, not just collisions
+        // with files that already exist on disk.
+        let mut planned: HashSet<PathBuf> = HashSet::new();
 
-        // Read all files from source directory
-        let entries = fs::read_dir(&self.source_dir)?;
-        
-        for entry in entries {
-            let entry = entry?;
-            let path = entry.path();
-            
-            if path.is_file() {
-                self.sort_single_file(&path, &mut report)?;
+        if !self.dry_run {
+            fs::create_dir_all(&self.target_dir)?;
+        }
+
+        let progress = if self.show_progress {
+            Some(ProgressBar::new(self.count_candidates()?))
+        } else {
+            None
+        };
+
+        self.walk_files(|path| {
+            self.sort_single_file(path, &mut report, &mut planned)?;
+            if let Some(pb) = &progress {
+                pb.inc(1);
             }
+            Ok(())
+        })?;
+
+        if let Some(pb) = progress {
+            pb.finish();
         }
 
         Ok(report)
     }
 
-    /// Sort a single file based on its extension and content
-    fn sort_single_file(&self, file_path: &Path, report: &mut SortingReport) -> Result<()> {
+    /// Count the files that would be sorted, without sorting them; used to size the progress bar
+    fn count_candidates(&self) -> Result<u64> {
+        let mut count = 0u64;
+        self.walk_files(|_| {
+            count += 1;
+            Ok(())
+        })?;
+        Ok(count)
+    }
+
+    /// Walk `source_dir` (honoring `recursive`, `max_depth`, and the include/exclude filters),
+    /// calling `visit` for every candidate file, and pruning directories that can't match
+    fn walk_files(&self, mut visit: impl FnMut(&Path) -> Result<()>) -> Result<()> {
+        // The target dir may live inside the source dir; never walk into it
+        let target_dir_abs = self.target_dir.canonicalize().unwrap_or_else(|_| self.target_dir.clone());
+
+        if self.recursive {
+            let mut dirs = vec![(self.source_dir.clone(), 0usize)];
+
+            while let Some((dir, depth)) = dirs.pop() {
+                let entries = fs::read_dir(&dir)?;
+
+                for entry in entries {
+                    let entry = entry?;
+                    let path = entry.path();
+
+                    let path_abs = path.canonicalize().unwrap_or_else(|_| path.clone());
+                    if path_abs.starts_with(&target_dir_abs) {
+                        continue;
+                    }
+
+                    let rel_path = path.strip_prefix(&self.source_dir).unwrap_or(&path);
+
+                    if path.is_dir() {
+                        let within_depth = self.max_depth.map_or(true, |max| depth < max);
+                        if within_depth && self.filters.should_descend(rel_path) {
+                            dirs.push((path, depth + 1));
+                        }
+                    } else if path.is_file() && self.filters.should_include(rel_path) {
+                        visit(&path)?;
+                    }
+                }
+            }
+        } else {
+            // Read all files from source directory
+            let entries = fs::read_dir(&self.source_dir)?;
+
+            for entry in entries {
+                let entry = entry?;
+                let path = entry.path();
+
+                let rel_path = path.strip_prefix(&self.source_dir).unwrap_or(&path);
+
+                if path.is_file() && self.filters.should_include(rel_path) {
+                    visit(&path)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Sort a single file based on its extension and content. `planned` accumulates every
+    /// target path this run has already claimed, so collisions between files sorted in the
+    /// same run are caught even when nothing has actually been written to disk yet (`dry_run`).
+    fn sort_single_file(&self, file_path: &Path, report: &mut SortingReport, planned: &mut HashSet<PathBuf>) -> Result<()> {
         let file_name = file_path.file_name()
             .and_then(|n| n.to_str())
             .unwrap_or("unknown");
 
+        let source_relative = file_path
+            .strip_prefix(&self.source_dir)
+            .unwrap_or(file_path)
+            .to_path_buf();
+
         let extension = file_path.extension()
             .and_then(|ext| ext.to_str())
             .unwrap_or("no_extension")
             .to_lowercase();
 
-        // Determine target folder based on extension
-        let target_folder = if extension == "html" || extension == "htm" {
-            // For HTML files, create subcategories
-            let category = self.detect_html_category(file_path)?;
-            format!("html/{}", category.folder_name())
-        } else {
-            // For other files, sort by extension
-            extension.clone()
+        // Evaluate configured rules first, falling back to plain extension-based sorting
+        let target_folder = match self.rules.evaluate(file_path)? {
+            Some(rule) => self.resolve_destination(&rule.destination, &extension, file_path)?,
+            None => extension.clone(),
         };
 
         // Create target directory
         let target_dir = self.target_dir.join(&target_folder);
-        fs::create_dir_all(&target_dir)?;
+        if !self.dry_run {
+            fs::create_dir_all(&target_dir)?;
+        }
+        report.note_directory(target_dir.clone());
+
+        // Resolve collisions before touching the filesystem
+        let target_path = match self.resolve_collision(&target_dir, file_name, planned) {
+            Some(path) => path,
+            None => {
+                if !self.dry_run {
+                    println!("Skipped {} (already exists in {})", file_path.display(), target_dir.display());
+                }
+                return Ok(());
+            }
+        };
 
-        // Move file to target directory
-        let target_path = target_dir.join(file_name);
-        fs::copy(file_path, &target_path)?;
+        if !self.dry_run {
+            self.apply_action(file_path, &target_path)?;
+        }
+        planned.insert(target_path.clone());
+
+        let final_name = target_path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or(file_name)
+            .to_string();
 
         // Update report
-        report.add_file_moved(extension, target_folder, file_name.to_string());
+        report.add_file_moved(extension, target_folder, final_name, source_relative, self.action);
+
+        if !self.dry_run {
+            println!("{} {} -> {}", self.action.past_tense(), file_path.display(), target_path.display());
+        }
+
+        Ok(())
+    }
+
+    /// Compute the final target path for `file_name` in `target_dir`, applying the collision
+    /// policy. `planned` is consulted alongside the real filesystem so a dry run simulates the
+    /// same outcome a real run would reach.
+    fn resolve_collision(&self, target_dir: &Path, file_name: &str, planned: &HashSet<PathBuf>) -> Option<PathBuf> {
+        let candidate = target_dir.join(file_name);
+        if !Self::is_taken(&candidate, planned) {
+            return Some(candidate);
+        }
+
+        match self.collision_policy {
+            CollisionPolicy::Overwrite => Some(candidate),
+            CollisionPolicy::Skip => None,
+            CollisionPolicy::Rename => Some(self.rename_for_collision(target_dir, file_name, planned)),
+        }
+    }
+
+    fn is_taken(candidate: &Path, planned: &HashSet<PathBuf>) -> bool {
+        candidate.exists() || planned.contains(candidate)
+    }
 
-        println!("Moved {} -> {}", file_path.display(), target_path.display());
+    /// Find a free name by appending an incrementing `" (n)"` suffix before the extension
+    fn rename_for_collision(&self, target_dir: &Path, file_name: &str, planned: &HashSet<PathBuf>) -> PathBuf {
+        let path = Path::new(file_name);
+        let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or(file_name);
+        let extension = path.extension().and_then(|e| e.to_str());
+
+        let mut attempt = 1usize;
+        loop {
+            let candidate_name = match extension {
+                Some(ext) => format!("{} ({}).{}", stem, attempt, ext),
+                None => format!("{} ({})", stem, attempt),
+            };
+
+            let candidate = target_dir.join(&candidate_name);
+            if !Self::is_taken(&candidate, planned) {
+                return candidate;
+            }
+            attempt += 1;
+        }
+    }
+
+    /// Place the file at `target_path` according to the configured `SortAction`
+    fn apply_action(&self, file_path: &Path, target_path: &Path) -> Result<()> {
+        match self.action {
+            SortAction::Copy => {
+                fs::copy(file_path, target_path)?;
+            }
+            SortAction::Hardlink => {
+                // Unlike `fs::copy`/`fs::rename`, `fs::hard_link` errors if the destination
+                // already exists, so an Overwrite collision needs the old file removed first.
+                if target_path.exists() {
+                    fs::remove_file(target_path)?;
+                }
+                fs::hard_link(file_path, target_path)?;
+            }
+            SortAction::Move => {
+                if fs::rename(file_path, target_path).is_err() {
+                    // Source and target are on different filesystems; fall back to copy + remove
+                    fs::copy(file_path, target_path)?;
+                    fs::remove_file(file_path)?;
+                }
+            }
+        }
 
         Ok(())
     }
 
+    /// Fill in the `{ext}` and `{category}` placeholders of a rule's destination template
+    fn resolve_destination(&self, template: &str, extension: &str, file_path: &Path) -> Result<String> {
+        let mut destination = template.replace("{ext}", extension);
+
+        if destination.contains("{category}") {
+            let category = self.detect_html_category(file_path)?;
+            destination = destination.replace("{category}", category.folder_name());
+        }
+
+        Ok(destination)
+    }
+
     /// Detect HTML file category based on filename and content
     fn detect_html_category(&self, file_path: &Path) -> Result<HtmlCategory> {
         let file_name = file_path.file_stem()
@@ -137,11 +444,24 @@ impl HtmlCategory {
     }
 }
 
+/// A single file that was sorted, along with where it came from
+#[derive(Debug, Clone)]
+pub struct SortedFile {
+    /// The file's final name in the target directory, after any collision renaming
+    pub filename: String,
+    /// Path of the file relative to the source directory it was sorted from
+    pub source_path: PathBuf,
+    /// How the file was placed into the target directory
+    pub action: SortAction,
+}
+
 /// Report of the sorting operation
 #[derive(Debug, Default)]
 pub struct SortingReport {
-    pub files_moved: HashMap<String, Vec<String>>,
+    pub files_moved: HashMap<String, Vec<SortedFile>>,
     pub total_files: usize,
+    /// Every target directory the plan would create (or did create, outside of `dry_run`)
+    pub directories: HashSet<PathBuf>,
 }
 
 impl SortingReport {
@@ -149,26 +469,32 @@ impl SortingReport {
         Self {
             files_moved: HashMap::new(),
             total_files: 0,
+            directories: HashSet::new(),
         }
     }
 
-    pub fn add_file_moved(&mut self, extension: String, folder: String, filename: String) {
+    pub fn add_file_moved(&mut self, extension: String, folder: String, filename: String, source_path: PathBuf, action: SortAction) {
         let key = format!("{} -> {}", extension, folder);
         self.files_moved.entry(key)
             .or_default()
-            .push(filename);
+            .push(SortedFile { filename, source_path, action });
         self.total_files += 1;
     }
 
+    pub fn note_directory(&mut self, directory: PathBuf) {
+        self.directories.insert(directory);
+    }
+
     pub fn print_summary(&self) {
         println!("\n=== File Sorting Summary ===");
         println!("Total files moved: {}", self.total_files);
+        println!("Directories: {}", self.directories.len());
         println!();
 
         for (category, files) in &self.files_moved {
             println!("{}: {} files", category, files.len());
             for file in files {
-                println!("  - {}", file);
+                println!("  - {} (from {})", file.filename, file.source_path.display());
             }
             println!();
         }
@@ -202,9 +528,162 @@ mod tests {
     #[test]
     fn test_sorting_report() {
         let mut report = SortingReport::new();
-        report.add_file_moved("txt".to_string(), "txt".to_string(), "test.txt".to_string());
+        report.add_file_moved("txt".to_string(), "txt".to_string(), "test.txt".to_string(), PathBuf::from("test.txt"), SortAction::Copy);
         
         assert_eq!(report.total_files, 1);
         assert!(report.files_moved.contains_key("txt -> txt"));
     }
+
+    #[test]
+    fn test_recursive_sort_finds_nested_files() {
+        let source = tempdir().unwrap();
+        let target = tempdir().unwrap();
+
+        let nested = source.path().join("nested");
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(nested.join("notes.txt"), "hello").unwrap();
+
+        let sorter = FileSorter::new(source.path(), target.path()).recursive(true);
+        let report = sorter.sort_files().unwrap();
+
+        assert_eq!(report.total_files, 1);
+        let files = report.files_moved.get("txt -> txt").unwrap();
+        assert_eq!(files[0].source_path, PathBuf::from("nested/notes.txt"));
+    }
+
+    #[test]
+    fn test_custom_rule_overrides_extension_sorting() {
+        let source = tempdir().unwrap();
+        let target = tempdir().unwrap();
+        fs::write(source.path().join("report.csv"), "a,b,c").unwrap();
+
+        let custom_rules = RuleSet::new(vec![Rule {
+            predicate: MatchPredicate::Extensions(["csv"].iter().map(|s| s.to_string()).collect()),
+            destination: "spreadsheets".to_string(),
+        }]);
+
+        let sorter = FileSorter::new(source.path(), target.path()).with_rules(custom_rules);
+        let report = sorter.sort_files().unwrap();
+
+        assert!(report.files_moved.contains_key("csv -> spreadsheets"));
+    }
+
+    #[test]
+    fn test_rename_collision_policy_avoids_overwrite() {
+        let source = tempdir().unwrap();
+        let target = tempdir().unwrap();
+        fs::write(source.path().join("report.txt"), "new").unwrap();
+        fs::create_dir_all(target.path().join("txt")).unwrap();
+        fs::write(target.path().join("txt").join("report.txt"), "existing").unwrap();
+
+        let sorter = FileSorter::new(source.path(), target.path()).collision_policy(CollisionPolicy::Rename);
+        let report = sorter.sort_files().unwrap();
+
+        let files = report.files_moved.get("txt -> txt").unwrap();
+        assert_eq!(files[0].filename, "report (1).txt");
+        assert_eq!(
+            fs::read_to_string(target.path().join("txt").join("report.txt")).unwrap(),
+            "existing"
+        );
+    }
+
+    #[test]
+    fn test_hardlink_action_overwrites_existing_collision() {
+        let source = tempdir().unwrap();
+        let target = tempdir().unwrap();
+        fs::write(source.path().join("report.txt"), "new").unwrap();
+        fs::create_dir_all(target.path().join("txt")).unwrap();
+        fs::write(target.path().join("txt").join("report.txt"), "existing").unwrap();
+
+        let sorter = FileSorter::new(source.path(), target.path())
+            .action(SortAction::Hardlink)
+            .collision_policy(CollisionPolicy::Overwrite);
+        let report = sorter.sort_files().unwrap();
+
+        let files = report.files_moved.get("txt -> txt").unwrap();
+        assert_eq!(files[0].filename, "report.txt");
+        assert_eq!(
+            fs::read_to_string(target.path().join("txt").join("report.txt")).unwrap(),
+            "new"
+        );
+    }
+
+    #[test]
+    fn test_move_action_removes_source_file() {
+        let source = tempdir().unwrap();
+        let target = tempdir().unwrap();
+        let source_file = source.path().join("notes.txt");
+        fs::write(&source_file, "hello").unwrap();
+
+        let sorter = FileSorter::new(source.path(), target.path()).action(SortAction::Move);
+        sorter.sort_files().unwrap();
+
+        assert!(!source_file.exists());
+        assert!(target.path().join("txt").join("notes.txt").exists());
+    }
+
+    #[test]
+    fn test_exclude_prunes_matching_subtree() {
+        let source = tempdir().unwrap();
+        let target = tempdir().unwrap();
+
+        let excluded = source.path().join("node_modules");
+        fs::create_dir_all(&excluded).unwrap();
+        fs::write(excluded.join("pkg.json"), "{}").unwrap();
+        fs::write(source.path().join("app.txt"), "hello").unwrap();
+
+        let sorter = FileSorter::new(source.path(), target.path())
+            .recursive(true)
+            .exclude(&["node_modules/**"])
+            .unwrap();
+        let report = sorter.sort_files().unwrap();
+
+        assert_eq!(report.total_files, 1);
+        assert!(!target.path().join("json").join("pkg.json").exists());
+    }
+
+    #[test]
+    fn test_dry_run_plans_without_touching_filesystem() {
+        let source = tempdir().unwrap();
+        let target = tempdir().unwrap();
+        fs::write(source.path().join("notes.txt"), "hello").unwrap();
+
+        let sorter = FileSorter::new(source.path(), target.path()).dry_run(true);
+        let report = sorter.sort_files().unwrap();
+
+        assert_eq!(report.total_files, 1);
+        assert!(report.directories.contains(&target.path().join("txt")));
+        assert!(!target.path().join("txt").exists());
+    }
+
+    #[test]
+    fn test_dry_run_detects_collisions_between_planned_files() {
+        let source = tempdir().unwrap();
+        let target = tempdir().unwrap();
+
+        fs::create_dir_all(source.path().join("a")).unwrap();
+        fs::create_dir_all(source.path().join("b")).unwrap();
+        fs::write(source.path().join("a").join("report.txt"), "a").unwrap();
+        fs::write(source.path().join("b").join("report.txt"), "b").unwrap();
+
+        let sorter = FileSorter::new(source.path(), target.path())
+            .recursive(true)
+            .dry_run(true)
+            .collision_policy(CollisionPolicy::Rename);
+        let report = sorter.sort_files().unwrap();
+
+        assert_eq!(report.total_files, 2);
+        let names: std::collections::HashSet<_> = report
+            .files_moved
+            .get("txt -> txt")
+            .unwrap()
+            .iter()
+            .map(|f| f.filename.clone())
+            .collect();
+        assert_eq!(
+            names,
+            ["report.txt".to_string(), "report (1).txt".to_string()].into_iter().collect()
+        );
+        assert!(!target.path().join("txt").exists());
+    }
 }
\ No newline at end of file
@@ -0,0 +1,216 @@
+use crate::Result;
+use regex::Regex;
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// A single condition a file must satisfy for a [`Rule`] to apply
+#[derive(Debug, Clone)]
+pub enum MatchPredicate {
+    /// Matches if the file name matches this compiled regex
+    FilenameRegex(Regex),
+    /// Matches if the file's (lowercased) extension is in this set
+    Extensions(HashSet<String>),
+    /// Matches if the file size in bytes falls within `[min, max]` (either bound optional)
+    SizeRange { min: Option<u64>, max: Option<u64> },
+    /// Matches if the file's content contains this substring
+    ContentContains(String),
+}
+
+/// A single sorting rule: a predicate plus a destination template
+///
+/// Destination templates may reference `{ext}` (the file's extension) and
+/// `{category}` (the HTML category, for HTML files) which are substituted
+/// once the rule has matched.
+#[derive(Debug, Clone)]
+pub struct Rule {
+    pub predicate: MatchPredicate,
+    pub destination: String,
+}
+
+impl Rule {
+    /// Check whether this rule applies to `file_path`
+    pub fn matches(&self, file_path: &Path) -> Result<bool> {
+        let matched = match &self.predicate {
+            MatchPredicate::FilenameRegex(re) => {
+                let file_name = file_path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+                re.is_match(file_name)
+            }
+            MatchPredicate::Extensions(exts) => {
+                let extension = file_path
+                    .extension()
+                    .and_then(|ext| ext.to_str())
+                    .unwrap_or("")
+                    .to_lowercase();
+                exts.contains(&extension)
+            }
+            MatchPredicate::SizeRange { min, max } => {
+                let size = fs::metadata(file_path)?.len();
+                min.map_or(true, |min| size >= min) && max.map_or(true, |max| size <= max)
+            }
+            MatchPredicate::ContentContains(needle) => {
+                let content = fs::read_to_string(file_path).unwrap_or_default();
+                content.contains(needle.as_str())
+            }
+        };
+
+        Ok(matched)
+    }
+}
+
+/// An ordered collection of [`Rule`]s, evaluated top to bottom
+#[derive(Debug, Clone, Default)]
+pub struct RuleSet {
+    pub rules: Vec<Rule>,
+}
+
+impl RuleSet {
+    pub fn new(rules: Vec<Rule>) -> Self {
+        Self { rules }
+    }
+
+    /// The HTML categorization that used to be hard-coded in `FileSorter`, expressed as a rule
+    pub fn default_rules() -> Self {
+        Self::new(vec![Rule {
+            predicate: MatchPredicate::Extensions(
+                ["html", "htm"].iter().map(|s| s.to_string()).collect(),
+            ),
+            destination: "html/{category}".to_string(),
+        }])
+    }
+
+    /// Load a rule set from a TOML or JSON config file, picked by the file's extension
+    pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let raw = fs::read_to_string(path)?;
+
+        let config: RuleSetConfig = match path.extension().and_then(|e| e.to_str()) {
+            Some("json") => serde_json::from_str(&raw)?,
+            _ => toml::from_str(&raw)?,
+        };
+
+        config.into_rule_set()
+    }
+
+    /// Return the first rule whose predicate matches `file_path`, if any
+    pub fn evaluate(&self, file_path: &Path) -> Result<Option<&Rule>> {
+        for rule in &self.rules {
+            if rule.matches(file_path)? {
+                return Ok(Some(rule));
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+/// On-disk representation of a [`RuleSet`], deserialized from TOML/JSON
+#[derive(Debug, Deserialize)]
+struct RuleSetConfig {
+    rule: Vec<RuleConfig>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RuleConfig {
+    filename_regex: Option<String>,
+    extensions: Option<Vec<String>>,
+    min_size: Option<u64>,
+    max_size: Option<u64>,
+    content_contains: Option<String>,
+    destination: String,
+}
+
+impl RuleSetConfig {
+    fn into_rule_set(self) -> Result<RuleSet> {
+        let mut rules = Vec::with_capacity(self.rule.len());
+
+        for rule in self.rule {
+            let predicate = if let Some(pattern) = rule.filename_regex {
+                MatchPredicate::FilenameRegex(Regex::new(&pattern)?)
+            } else if let Some(extensions) = rule.extensions {
+                MatchPredicate::Extensions(extensions.into_iter().map(|e| e.to_lowercase()).collect())
+            } else if let Some(needle) = rule.content_contains {
+                MatchPredicate::ContentContains(needle)
+            } else if rule.min_size.is_some() || rule.max_size.is_some() {
+                MatchPredicate::SizeRange {
+                    min: rule.min_size,
+                    max: rule.max_size,
+                }
+            } else {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!(
+                        "rule with destination `{}` has no predicate (expected one of filename_regex, extensions, min_size/max_size, content_contains)",
+                        rule.destination
+                    ),
+                )
+                .into());
+            };
+
+            rules.push(Rule {
+                predicate,
+                destination: rule.destination,
+            });
+        }
+
+        Ok(RuleSet::new(rules))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_load_from_toml_file() {
+        let dir = tempdir().unwrap();
+        let config_path = dir.path().join("rules.toml");
+        fs::write(
+            &config_path,
+            r#"
+            [[rule]]
+            extensions = ["png", "jpg"]
+            destination = "images"
+            "#,
+        )
+        .unwrap();
+
+        let rules = RuleSet::load_from_file(&config_path).unwrap();
+        assert_eq!(rules.rules.len(), 1);
+        assert_eq!(rules.rules[0].destination, "images");
+    }
+
+    #[test]
+    fn test_load_from_json_file() {
+        let dir = tempdir().unwrap();
+        let config_path = dir.path().join("rules.json");
+        fs::write(
+            &config_path,
+            r#"{"rule": [{"extensions": ["png", "jpg"], "destination": "images"}]}"#,
+        )
+        .unwrap();
+
+        let rules = RuleSet::load_from_file(&config_path).unwrap();
+        assert_eq!(rules.rules.len(), 1);
+        assert_eq!(rules.rules[0].destination, "images");
+    }
+
+    #[test]
+    fn test_rule_without_predicate_is_rejected() {
+        let dir = tempdir().unwrap();
+        let config_path = dir.path().join("rules.toml");
+        fs::write(
+            &config_path,
+            r#"
+            [[rule]]
+            destination = "catch-all"
+            "#,
+        )
+        .unwrap();
+
+        assert!(RuleSet::load_from_file(&config_path).is_err());
+    }
+}
@@ -0,0 +1,135 @@
+use super::{FileSorter, SortingReport};
+use crate::Result;
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::Instant;
+
+impl FileSorter {
+    /// Run an initial `sort_files`, then keep watching `source_dir` and sort new or renamed
+    /// files as they appear.
+    ///
+    /// Runs forever (this is meant for long-lived directories like `Downloads`); bursts of
+    /// events for the same path are coalesced using the configured debounce period so a single
+    /// editor save doesn't trigger several moves.
+    pub fn watch(&self) -> Result<()> {
+        self.sort_files()?;
+
+        let target_dir_abs = self.target_dir.canonicalize().unwrap_or_else(|_| self.target_dir.clone());
+
+        let (tx, rx) = mpsc::channel();
+        let mut watcher: RecommendedWatcher = notify::recommended_watcher(move |event: notify::Result<Event>| {
+            if let Ok(event) = event {
+                let _ = tx.send(event);
+            }
+        })?;
+
+        let recursive_mode = if self.recursive {
+            RecursiveMode::Recursive
+        } else {
+            RecursiveMode::NonRecursive
+        };
+        watcher.watch(&self.source_dir, recursive_mode)?;
+
+        let mut pending: HashMap<PathBuf, Instant> = HashMap::new();
+
+        loop {
+            match rx.recv_timeout(self.debounce) {
+                Ok(event) if is_relevant(&event.kind) => {
+                    for path in event.paths {
+                        let path_abs = path.canonicalize().unwrap_or_else(|_| path.clone());
+                        if !path_abs.starts_with(&target_dir_abs) {
+                            pending.insert(path, Instant::now());
+                        }
+                    }
+                }
+                Ok(_) => {}
+                Err(mpsc::RecvTimeoutError::Timeout) => {}
+                Err(mpsc::RecvTimeoutError::Disconnected) => break,
+            }
+
+            let ready: Vec<PathBuf> = pending
+                .iter()
+                .filter(|(_, seen)| seen.elapsed() >= self.debounce)
+                .map(|(path, _)| path.clone())
+                .collect();
+
+            for path in ready {
+                pending.remove(&path);
+
+                if self.should_sort_watched_path(&path) {
+                    let mut report = SortingReport::new();
+                    let mut planned = std::collections::HashSet::new();
+                    if let Err(err) = self.sort_single_file(&path, &mut report, &mut planned) {
+                        eprintln!("Failed to sort {}: {}", path.display(), err);
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Whether a path surfaced by the watcher should actually be sorted: it must be a file, and
+    /// pass the same include/exclude filters `sort_files` applies during its own walk.
+    fn should_sort_watched_path(&self, path: &Path) -> bool {
+        let rel_path = path.strip_prefix(&self.source_dir).unwrap_or(path);
+        if !path.is_file() || !self.filters.should_include(rel_path) {
+            return false;
+        }
+
+        // The walk never reaches files below a pruned directory or past `max_depth`, so every
+        // directory between `source_dir` and the file has to pass the same check
+        let dirs: Vec<&Path> = rel_path
+            .ancestors()
+            .skip(1)
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .collect();
+        let within_depth = self.max_depth.map_or(true, |max| dirs.len() <= max);
+        within_depth && dirs.iter().all(|dir| self.filters.should_descend(dir))
+    }
+}
+
+/// Only create and rename events should trigger a re-sort; content modifications of a file
+/// that's already been sorted are left alone.
+fn is_relevant(kind: &EventKind) -> bool {
+    matches!(kind, EventKind::Create(_) | EventKind::Modify(notify::event::ModifyKind::Name(_)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_should_sort_watched_path_honors_excludes() {
+        let source = tempdir().unwrap();
+        let target = tempdir().unwrap();
+        let excluded = source.path().join("node_modules").join("pkg.json");
+        fs::create_dir_all(excluded.parent().unwrap()).unwrap();
+        fs::write(&excluded, "{}").unwrap();
+
+        let sorter = FileSorter::new(source.path(), target.path())
+            .exclude(&["node_modules/**"])
+            .unwrap();
+
+        assert!(!sorter.should_sort_watched_path(&excluded));
+    }
+
+    #[test]
+    fn test_should_sort_watched_path_honors_includes() {
+        let source = tempdir().unwrap();
+        let target = tempdir().unwrap();
+        let png = source.path().join("pic.png");
+        let txt = source.path().join("notes.txt");
+        fs::write(&png, "png").unwrap();
+        fs::write(&txt, "notes").unwrap();
+
+        let sorter = FileSorter::new(source.path(), target.path()).include(&["*.png"]).unwrap();
+
+        assert!(sorter.should_sort_watched_path(&png));
+        assert!(!sorter.should_sort_watched_path(&txt));
+    }
+}